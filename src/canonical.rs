@@ -0,0 +1,209 @@
+use super::*;
+use core::cmp::Ordering;
+use core::hash::{Hash, Hasher};
+
+/// A wrapper around `Uri` that compares and hashes the canonical form of the uri.
+///
+/// Two uris are considered equal if they only differ in
+/// - the case of the scheme and the host
+/// - the case of hex digits in percent-encodings ("%2f" and "%2F")
+/// - an explicit port that is the default port of the scheme
+///
+/// This makes it possible to deduplicate equivalent uris
+/// in a `BTreeMap` or `HashMap`.
+///
+/// # Examples
+///
+/// ```rust
+/// use nom_uri::{CanonicalUri, Uri};
+///
+/// # fn run() -> Result<(), nom_uri::Error> {
+/// let a = CanonicalUri(Uri::parse("HTTP://Example.com:80/a%2f")?);
+/// let b = CanonicalUri(Uri::parse("http://example.com/a%2F")?);
+/// assert_eq!(a, b);
+/// # Ok(())
+/// # }
+/// # run().unwrap();
+/// ```
+#[derive(Debug)]
+pub struct CanonicalUri<'uri>(pub Uri<'uri>);
+
+/// A configurable comparison of uris.
+///
+/// Unlike the derived `Ord` of `Uri`, components can be ignored
+/// or compared case insensitive.
+/// All other components are compared byte by byte.
+///
+/// # Examples
+///
+/// ```rust
+/// use core::cmp::Ordering;
+/// use nom_uri::{Uri, UriComparator};
+///
+/// # fn run() -> Result<(), nom_uri::Error> {
+/// let comparator = UriComparator {
+///     ignore_fragment: true,
+///     ..UriComparator::default()
+/// };
+/// let a = Uri::parse("http://example.com/x#a")?;
+/// let b = Uri::parse("http://example.com/x#b")?;
+/// assert_eq!(comparator.compare(&a, &b), Ordering::Equal);
+/// # Ok(())
+/// # }
+/// # run().unwrap();
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UriComparator {
+    /// Do not compare the fragments.
+    pub ignore_fragment: bool,
+    /// Do not compare the queries.
+    pub ignore_query: bool,
+    /// Compare the hosts ignoring ASCII case.
+    /// Hosts are always compared by their string, regardless of their kind.
+    pub case_insensitive_host: bool,
+}
+impl UriComparator {
+    /// Compare two uris with the configured equivalence.
+    pub fn compare(&self, a: &Uri, b: &Uri) -> Ordering {
+        a.scheme()
+            .cmp(b.scheme())
+            .then_with(|| a.userinfo().cmp(&b.userinfo()))
+            .then_with(|| match (a.host(), b.host()) {
+                (Some(a), Some(b)) => {
+                    let lowercase = self.case_insensitive_host;
+                    host_bytes(a, lowercase).cmp(host_bytes(b, lowercase))
+                }
+                (a, b) => a.is_some().cmp(&b.is_some()),
+            })
+            .then_with(|| a.port().cmp(&b.port()))
+            .then_with(|| a.path().cmp(b.path()))
+            .then_with(|| match self.ignore_query {
+                true => Ordering::Equal,
+                false => a.query().cmp(&b.query()),
+            })
+            .then_with(|| match self.ignore_fragment {
+                true => Ordering::Equal,
+                false => a.fragment().cmp(&b.fragment()),
+            })
+    }
+}
+/// The bytes of the host string, lowercased if `lowercase` is set.
+/// The kind of the host is ignored.
+fn host_bytes(host: Host<'_>, lowercase: bool) -> impl Iterator<Item = u8> + '_ {
+    match host {
+        Host::RegistryName(s) | Host::V4(s) | Host::V6(s) | Host::VFuture(s) => {
+            s.bytes().map(move |byte| match lowercase {
+                true => byte.to_ascii_lowercase(),
+                false => byte,
+            })
+        }
+    }
+}
+
+/// Iterator over the bytes of a component in canonical form.
+#[derive(Clone)]
+pub(crate) struct Canonical<'a> {
+    bytes: &'a [u8],
+    lowercase: bool,
+    // remaining hex digits of the current percent-encoding
+    hex: u8,
+}
+impl<'a> Iterator for Canonical<'a> {
+    type Item = u8;
+    fn next(&mut self) -> Option<u8> {
+        let (&byte, rest) = self.bytes.split_first()?;
+        self.bytes = rest;
+        if self.hex > 0 {
+            self.hex -= 1;
+            Some(byte.to_ascii_uppercase())
+        } else if byte == b'%' {
+            self.hex = 2;
+            Some(byte)
+        } else if self.lowercase {
+            Some(byte.to_ascii_lowercase())
+        } else {
+            Some(byte)
+        }
+    }
+}
+/// Uppercase the hex digits of percent-encodings and
+/// optionally lowercase all other characters.
+pub(crate) fn canonical(component: &str, lowercase: bool) -> Canonical<'_> {
+    Canonical {
+        bytes: component.as_bytes(),
+        lowercase,
+        hex: 0,
+    }
+}
+fn cmp_component(a: Option<&str>, b: Option<&str>, lowercase: bool) -> Ordering {
+    match (a, b) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Less,
+        (Some(_), None) => Ordering::Greater,
+        (Some(a), Some(b)) => canonical(a, lowercase).cmp(canonical(b, lowercase)),
+    }
+}
+fn hash_component<H: Hasher>(component: Option<&str>, lowercase: bool, state: &mut H) {
+    match component {
+        None => state.write_u8(0),
+        Some(component) => {
+            state.write_u8(1);
+            canonical(component, lowercase).for_each(|byte| state.write_u8(byte));
+            // 0xFF is no ascii character and terminates the component
+            state.write_u8(0xFF);
+        }
+    }
+}
+/// The port if it differs from the default port of the scheme.
+fn explicit_port(uri: &Uri) -> Option<u16> {
+    match uri.port() {
+        Some(port) if Some(port) == default_port(uri.scheme(), DEFAULT_PORTS) => None,
+        port => port,
+    }
+}
+impl<'uri> CanonicalUri<'uri> {
+    fn host(&self) -> Option<&str> {
+        match self.0.host() {
+            Some(Host::RegistryName(s))
+            | Some(Host::V4(s))
+            | Some(Host::V6(s))
+            | Some(Host::VFuture(s)) => Some(s),
+            None => None,
+        }
+    }
+}
+impl<'uri> Ord for CanonicalUri<'uri> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (a, b) = (&self.0, &other.0);
+        cmp_component(Some(a.scheme()), Some(b.scheme()), true)
+            .then_with(|| cmp_component(a.userinfo(), b.userinfo(), false))
+            .then_with(|| cmp_component(self.host(), other.host(), true))
+            .then_with(|| explicit_port(a).cmp(&explicit_port(b)))
+            .then_with(|| cmp_component(Some(a.path()), Some(b.path()), false))
+            .then_with(|| cmp_component(a.query(), b.query(), false))
+            .then_with(|| cmp_component(a.fragment(), b.fragment(), false))
+    }
+}
+impl<'uri> PartialOrd for CanonicalUri<'uri> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl<'uri> PartialEq for CanonicalUri<'uri> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+impl<'uri> Eq for CanonicalUri<'uri> {}
+impl<'uri> Hash for CanonicalUri<'uri> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let uri = &self.0;
+        hash_component(Some(uri.scheme()), true, state);
+        hash_component(uri.userinfo(), false, state);
+        hash_component(self.host(), true, state);
+        explicit_port(uri).hash(state);
+        hash_component(Some(uri.path()), false, state);
+        hash_component(uri.query(), false, state);
+        hash_component(uri.fragment(), false, state);
+    }
+}
@@ -2,9 +2,43 @@
 pub enum Error {
     ParseError,
     ParseIncomplete,
-    BufferToSmall,
+    /// The output needs `needed` bytes but the buffer has only `available`.
+    BufferTooSmall {
+        needed: usize,
+        available: usize,
+    },
     Conversion(core::str::Utf8Error),
     NoAuthority,
+    /// The input starts with a valid uri but has unparsed bytes
+    /// beginning at `offset`.
+    TrailingInput {
+        offset: usize,
+    },
+    /// The host contains non ascii characters that cannot be percent-encoded.
+    NonAsciiHost,
+    /// A component is longer than the allowed maximum of `max_len` bytes.
+    TooLong {
+        max_len: usize,
+    },
+    /// The path has more than the allowed maximum of `max` segments.
+    TooManySegments {
+        max: usize,
+    },
+    /// The pattern has `needed` captures but the output holds only `available`.
+    TooManyCaptures {
+        needed: usize,
+        available: usize,
+    },
+    /// The operation is not supported for the scheme of the uri.
+    UnsupportedScheme,
+    /// The input contains a control character or DEL at `offset`.
+    InvalidCharacter {
+        offset: usize,
+    },
+    /// The uri has a userinfo but credentials are not allowed.
+    UserinfoNotAllowed,
+    /// Nothing follows the scheme of the uri.
+    EmptySchemeSpecificPart,
 }
 
 pub type ParserError<'a> = (&'a [u8], nom::error::ErrorKind);
@@ -24,12 +58,37 @@ impl core::fmt::Debug for Error {
         match self {
             Error::ParseError => write!(f, "Could not parse input"),
             Error::ParseIncomplete => write!(f, "Incomplete parsing.",),
-            Error::BufferToSmall => write!(f, "Output does not fit in buffer."),
+            Error::BufferTooSmall { needed, available } => write!(
+                f,
+                "Output of {} bytes does not fit in buffer of {} bytes.",
+                needed, available
+            ),
             Error::Conversion(e) => write!(f, "Tried to convert non utf8 to string: {}", e),
             Error::NoAuthority => write!(
                 f,
                 "Tried to set authority field on an uri without authority."
             ),
+            Error::TrailingInput { offset } => {
+                write!(f, "Unexpected trailing input at offset {}.", offset)
+            }
+            Error::NonAsciiHost => write!(f, "Host contains non ascii characters."),
+            Error::TooLong { max_len } => {
+                write!(f, "Component is longer than {} bytes.", max_len)
+            }
+            Error::TooManySegments { max } => {
+                write!(f, "Path has more than {} segments.", max)
+            }
+            Error::TooManyCaptures { needed, available } => write!(
+                f,
+                "Pattern has {} captures but only {} fit in the output.",
+                needed, available
+            ),
+            Error::UnsupportedScheme => write!(f, "Operation is not supported for this scheme."),
+            Error::InvalidCharacter { offset } => {
+                write!(f, "Invalid control character at offset {}.", offset)
+            }
+            Error::UserinfoNotAllowed => write!(f, "Uri contains a userinfo."),
+            Error::EmptySchemeSpecificPart => write!(f, "Uri has nothing after the scheme."),
         }
     }
 }
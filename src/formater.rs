@@ -1,8 +1,27 @@
 use super::*;
 use core::fmt;
+use core::fmt::Write;
 
+/// The alternate form (`{:#}`) percent-decodes path, query and fragment
+/// for human reading. Decoded bytes that are no valid utf8 stay encoded.
 impl<'uri> fmt::Display for Uri<'uri> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            write!(f, "{}:", self.scheme())?;
+            if let Some(authority) = self.authority {
+                write!(f, "//{}", authority)?;
+            }
+            write_decoded(f, self.path())?;
+            if let Some(query) = self.query() {
+                f.write_char('?')?;
+                write_decoded(f, query)?;
+            }
+            if let Some(fragment) = self.fragment() {
+                f.write_char('#')?;
+                write_decoded(f, fragment)?;
+            }
+            return Ok(());
+        }
         write!(
             f,
             "{}:{}{}{}{}{}{}{}",
@@ -21,6 +40,41 @@ impl<'uri> fmt::Display for Uri<'uri> {
         )
     }
 }
+impl<'uri> fmt::Display for Reference<'uri> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(authority) = self.authority {
+            write!(f, "//{}", authority)?;
+        }
+        write!(f, "{}", self.path)?;
+        if let Some(query) = self.query {
+            write!(f, "?{}", query)?;
+        }
+        if let Some(fragment) = self.fragment {
+            write!(f, "#{}", fragment)?;
+        }
+        Ok(())
+    }
+}
+/// Prints the components as returned by the accessors
+/// instead of the internal representation.
+impl<'uri> fmt::Debug for Uri<'uri> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Uri")
+            .field("scheme", &self.scheme())
+            .field("authority", &self.authority.map(Quoted))
+            .field("path", &self.path())
+            .field("query", &self.query())
+            .field("fragment", &self.fragment())
+            .finish()
+    }
+}
+/// Debug the display form of the inner value as string.
+struct Quoted<T>(T);
+impl<T: fmt::Display> fmt::Debug for Quoted<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "\"{}\"", self.0)
+    }
+}
 impl<'uri> fmt::Display for Authority<'uri> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -71,30 +125,143 @@ impl<'uri> fmt::Display for Query<'uri> {
         write!(f, "{}", self.0)
     }
 }
+/// Write the percent-decoded form of `encoded`.
+///
+/// Multi-byte characters are collected until they are complete.
+/// Bytes that do not form valid utf8 are written percent-encoded again.
+fn write_decoded(f: &mut fmt::Formatter<'_>, encoded: &str) -> fmt::Result {
+    let mut pending = [0u8; 4];
+    let mut len = 0;
+    for byte in percent::PercentDecode::new(encoded) {
+        pending[len] = byte;
+        len += 1;
+        match core::str::from_utf8(&pending[..len]) {
+            Ok(decoded) => {
+                f.write_str(decoded)?;
+                len = 0;
+            }
+            // incomplete character, wait for the next byte
+            Err(e) if e.error_len().is_none() => {}
+            Err(_) => {
+                // the last byte broke the character, keep it for a new one
+                for byte in &pending[..len - 1] {
+                    write!(f, "%{:02X}", byte)?;
+                }
+                if byte.is_ascii() {
+                    f.write_char(byte as char)?;
+                    len = 0;
+                } else {
+                    pending[0] = byte;
+                    len = 1;
+                }
+            }
+        }
+    }
+    for byte in &pending[..len] {
+        write!(f, "%{:02X}", byte)?;
+    }
+    Ok(())
+}
+/// A `fmt::Write` into a byte slice.
+///
+/// Writing never fails: output that does not fit is dropped but still counted,
+/// so the needed size is known after formatting.
 pub struct Buffer<'a> {
     buffer: &'a mut [u8],
     cursor: usize,
+    needed: usize,
 }
 impl<'a> Buffer<'a> {
     pub fn new(buffer: &'a mut [u8]) -> Self {
-        Self { buffer, cursor: 0 }
+        Self {
+            buffer,
+            cursor: 0,
+            needed: 0,
+        }
+    }
+    /// Number of bytes written so far, including those that did not fit.
+    pub fn written(&self) -> usize {
+        self.needed
     }
-    pub fn buffer(self) -> &'a mut [u8] {
+    /// The written bytes or `Error::BufferTooSmall` if not everything fit.
+    pub fn finish(self) -> Result<&'a mut [u8], Error> {
+        if self.needed > self.buffer.len() {
+            return Err(Error::BufferTooSmall {
+                needed: self.needed,
+                available: self.buffer.len(),
+            });
+        }
         let (o, _) = self.buffer.split_at_mut(self.cursor);
-        o
+        Ok(o)
     }
 }
 impl<'a> fmt::Write for Buffer<'a> {
     fn write_str(&mut self, s: &str) -> fmt::Result {
         let s = s.as_bytes();
-        if self.buffer.len() - self.cursor < s.len() {
-            Err(fmt::Error)
-        } else {
-            for i in 0..s.len() {
-                self.buffer[self.cursor] = s[i];
-                self.cursor += 1;
-            }
+        self.needed += s.len();
+        if self.needed <= self.buffer.len() {
+            self.buffer[self.cursor..self.needed].copy_from_slice(s);
+            self.cursor = self.needed;
+        }
+        Ok(())
+    }
+}
+/// Writer that passes at most `remaining` bytes to the inner writer.
+pub struct Truncated<'w, W: Write> {
+    inner: &'w mut W,
+    remaining: usize,
+    truncated: bool,
+}
+impl<'w, W: Write> Truncated<'w, W> {
+    pub fn new(inner: &'w mut W, max: usize) -> Self {
+        Self {
+            inner,
+            remaining: max,
+            truncated: false,
+        }
+    }
+    /// Whether some output was dropped.
+    pub fn truncated(&self) -> bool {
+        self.truncated
+    }
+}
+impl<'w, W: Write> fmt::Write for Truncated<'w, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if s.len() <= self.remaining {
+            self.remaining -= s.len();
+            return self.inner.write_str(s);
+        }
+        self.truncated = true;
+        // do not split a multi byte character
+        let mut end = self.remaining;
+        while !s.is_char_boundary(end) {
+            end -= 1;
+        }
+        self.remaining = 0;
+        self.inner.write_str(&s[..end])
+    }
+}
+/// Write to the buffer and return the number of written bytes.
+pub fn write_with<F>(buffer: &mut [u8], write: F) -> Result<usize, Error>
+where
+    F: FnOnce(&mut Buffer) -> fmt::Result,
+{
+    let mut buffer = Buffer::new(buffer);
+    // a Buffer never fails, so only a failing Display implementation returns an error
+    write(&mut buffer).map_err(|_| Error::ParseError)?;
+    Ok(buffer.finish()?.len())
+}
+/// Count the bytes of the formatted arguments.
+#[cfg(feature = "heapless")]
+pub fn formatted_len(args: fmt::Arguments) -> usize {
+    struct Counter(usize);
+    impl fmt::Write for Counter {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            self.0 += s.len();
             Ok(())
         }
     }
+    let mut counter = Counter(0);
+    let _ = counter.write_fmt(args);
+    counter.0
 }
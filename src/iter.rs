@@ -0,0 +1,103 @@
+/// Iterator over the `;`-delimited matrix parameters of a single path segment.
+///
+/// Created by [`Uri::matrix_params`](crate::Uri::matrix_params).
+/// Parameters without a `=` are returned with an empty value.
+#[derive(Debug, Clone)]
+pub struct MatrixParams<'uri> {
+    params: Option<core::str::Split<'uri, char>>,
+}
+impl<'uri> MatrixParams<'uri> {
+    pub(crate) fn new(segment: Option<&'uri str>) -> Self {
+        let params = segment.and_then(|segment| {
+            segment
+                .find(';')
+                .map(|start| segment[start + 1..].split(';'))
+        });
+        Self { params }
+    }
+}
+impl<'uri> Iterator for MatrixParams<'uri> {
+    type Item = (&'uri str, &'uri str);
+    fn next(&mut self) -> Option<Self::Item> {
+        let params = self.params.as_mut()?;
+        loop {
+            match params.next()? {
+                "" => continue,
+                param => return Some(split_pair(param)),
+            }
+        }
+    }
+}
+/// Split a `key=value` pair on the first '='.
+/// A missing '=' results in an empty value.
+pub(crate) fn split_pair(pair: &str) -> (&str, &str) {
+    match pair.find('=') {
+        Some(i) => (&pair[..i], &pair[i + 1..]),
+        None => (pair, ""),
+    }
+}
+/// Iterator over the `&`-delimited `key=value` pairs of a query.
+///
+/// Created by [`Uri::query_pairs`](crate::Uri::query_pairs).
+/// Keys and values are returned percent-encoded, as they appear in the uri.
+#[derive(Debug, Clone)]
+pub struct QueryPairs<'uri> {
+    pairs: Option<core::str::Split<'uri, char>>,
+}
+impl<'uri> QueryPairs<'uri> {
+    pub(crate) fn new(query: Option<&'uri str>) -> Self {
+        Self {
+            pairs: query.map(|query| query.split('&')),
+        }
+    }
+}
+impl<'uri> Iterator for QueryPairs<'uri> {
+    type Item = (&'uri str, &'uri str);
+    fn next(&mut self) -> Option<Self::Item> {
+        let pairs = self.pairs.as_mut()?;
+        loop {
+            match pairs.next()? {
+                "" => continue,
+                pair => return Some(split_pair(pair)),
+            }
+        }
+    }
+}
+/// Iterator over the uris of a separated list.
+///
+/// Created by [`parse_list`](crate::parse_list).
+#[derive(Debug, Clone)]
+pub struct UriListIter<'a, 's> {
+    tokens: core::str::Split<'a, &'s [char]>,
+}
+impl<'a, 's> Iterator for UriListIter<'a, 's> {
+    type Item = Result<crate::Uri<'a>, crate::Error>;
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.tokens.next()? {
+                "" => continue,
+                token => return Some(crate::Uri::parse(token)),
+            }
+        }
+    }
+}
+/// Parse a list of uris delimited by any of the `separators`.
+///
+/// Empty tokens are skipped, so repeated separators are allowed.
+/// Since uris cannot contain whitespace, whitespace is a safe separator.
+///
+/// # Examples
+///
+/// ```rust
+/// use nom_uri::parse_list;
+///
+/// let mut uris = parse_list("https://a.example, https://b.example", &[' ', ',']);
+/// assert_eq!(uris.next().unwrap().unwrap().host_str(), Some("a.example"));
+/// assert_eq!(uris.next().unwrap().unwrap().host_str(), Some("b.example"));
+/// assert!(uris.next().is_none());
+/// ```
+pub fn parse_list<'a, 's>(input: &'a str, separators: &'s [char]) -> UriListIter<'a, 's> {
+    UriListIter {
+        tokens: input.split(separators),
+    }
+}
@@ -0,0 +1,121 @@
+/// Syntax-based normalization based on http://www.faqs.org/rfcs/rfc3986.html
+/// Section 6.2.2.
+use super::*;
+use core::fmt;
+use core::fmt::Write;
+use formater::write_with;
+
+/// Options for [`Uri::normalize`](struct.Uri.html#method.normalize).
+///
+/// Case normalization is always applied,
+/// every option enables an additional normalization step.
+/// The default enables no additional steps.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Normalization {
+    /// Remove a single trailing '.' from a registry name host
+    /// (`example.com.` becomes `example.com`).
+    pub strip_trailing_dot: bool,
+    /// Remove the "." and ".." segments of an absolute path
+    /// (RFC 3986 Section 5.2.4).
+    ///
+    /// Only literal dots are dot-segments,
+    /// percent-encoded dots like `%2E%2E` are kept.
+    pub remove_dot_segments: bool,
+    /// Write an empty path as '/' for uris with a special scheme
+    /// (see [`Uri::is_special`](struct.Uri.html#method.is_special))
+    /// and an authority, so `https://example.com` becomes `https://example.com/`.
+    pub empty_path_to_slash: bool,
+    /// Remove a port that is the default port of the scheme
+    /// (see [`DEFAULT_PORTS`](constant.DEFAULT_PORTS.html)),
+    /// so `https://example.com:443/` becomes `https://example.com/`.
+    pub omit_default_port: bool,
+    /// Write IPv6 hosts in the canonical form of RFC 5952
+    /// (lowercase hex digits without leading zeros and the longest run of
+    /// zeros compressed to "::"),
+    /// so `[2001:DB8:0:0:0:0:0:1]` becomes `[2001:db8::1]`.
+    pub compress_ipv6: bool,
+}
+
+pub(crate) fn normalize<'b>(
+    uri: &Uri,
+    options: &Normalization,
+    buffer: &'b mut [u8],
+) -> Result<Uri<'b>, Error> {
+    let path = match uri.path() {
+        "" if options.empty_path_to_slash && uri.has_authority() && uri.is_special() => "/",
+        path => path,
+    };
+    let mut path_range = 0..0;
+    let mut written = write_with(buffer, |w| {
+        write_prefix(uri, options, w)?;
+        let start = w.written();
+        write_canonical(w, path, false)?;
+        path_range = start..w.written();
+        write_suffix(uri, w)
+    })?;
+    if options.remove_dot_segments && path.starts_with('/') {
+        written = resolve::remove_path_dot_segments(buffer, path_range, written);
+    }
+    let buffer: &'b [u8] = buffer;
+    Uri::parse_bytes(&buffer[..written])
+}
+/// Write scheme and authority.
+fn write_prefix<W: Write>(uri: &Uri, options: &Normalization, w: &mut W) -> fmt::Result {
+    write_canonical(w, uri.scheme, true)?;
+    w.write_char(':')?;
+    if let Some(authority) = uri.authority {
+        w.write_str("//")?;
+        if let Some(userinfo) = authority.userinfo {
+            write_canonical(w, userinfo, false)?;
+            w.write_char('@')?;
+        }
+        match (authority.host, compressed_ipv6(authority.host, options)) {
+            (Host::RegistryName(mut name), _) => {
+                if options.strip_trailing_dot && name.len() > 1 && name.ends_with('.') {
+                    name = &name[..name.len() - 1];
+                }
+                write_canonical(w, name, true)?;
+            }
+            (Host::V4(address), _) => w.write_str(address)?,
+            // the Display of Ipv6Addr follows RFC 5952
+            (Host::V6(_), Some(address)) => write!(w, "[{}]", address)?,
+            (Host::V6(address), _) | (Host::VFuture(address), _) => {
+                w.write_char('[')?;
+                write_canonical(w, address, true)?;
+                w.write_char(']')?;
+            }
+        }
+        let omit_port = options.omit_default_port
+            && uri.port().is_some()
+            && uri.port() == default_port(uri.scheme, DEFAULT_PORTS);
+        if let (Some(port), false) = (authority.port, omit_port) {
+            write!(w, ":{}", port)?;
+        }
+    }
+    Ok(())
+}
+/// The parsed IPv6 address of the host if it should be compressed.
+fn compressed_ipv6(host: Host, options: &Normalization) -> Option<core::net::Ipv6Addr> {
+    match host {
+        Host::V6(address) if options.compress_ipv6 => address.parse().ok(),
+        _ => None,
+    }
+}
+/// Write query and fragment.
+fn write_suffix<W: Write>(uri: &Uri, w: &mut W) -> fmt::Result {
+    if let Some(query) = uri.query() {
+        w.write_char('?')?;
+        write_canonical(w, query, false)?;
+    }
+    if let Some(fragment) = uri.fragment() {
+        w.write_char('#')?;
+        write_canonical(w, fragment, false)?;
+    }
+    Ok(())
+}
+fn write_canonical<W: Write>(w: &mut W, component: &str, lowercase: bool) -> fmt::Result {
+    for byte in canonical::canonical(component, lowercase) {
+        w.write_char(byte as char)?;
+    }
+    Ok(())
+}
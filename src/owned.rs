@@ -0,0 +1,79 @@
+use super::*;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+/// An owned uri that is not bound to the lifetime of the parsed input.
+///
+/// Created by [`Uri::into_owned`](struct.Uri.html#method.into_owned).
+/// Requires the `alloc` feature.
+///
+/// # Examples
+///
+/// ```rust
+/// use nom_uri::{Uri, UriString};
+///
+/// fn owned() -> UriString {
+///     let input = String::from("https://example.com/a");
+///     Uri::parse(&input).unwrap().into_owned().unwrap()
+/// }
+/// assert_eq!(owned().as_uri().path(), "/a");
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct UriString(String);
+
+impl UriString {
+    /// Parse an owned string as uri.
+    pub fn parse(input: String) -> Result<Self, Error> {
+        Uri::parse(&input)?;
+        Ok(UriString(input))
+    }
+    /// Return the uri borrowing from this string.
+    pub fn as_uri(&self) -> Uri<'_> {
+        // the string was validated on construction
+        Uri::parse(&self.0).expect("UriString contains a valid uri")
+    }
+    /// Return the serialized uri.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+impl<'uri> Uri<'uri> {
+    /// Serialize this uri into an owned [`UriString`](struct.UriString.html).
+    /// Requires the `alloc` feature.
+    ///
+    /// The serialization is parsed again, so an uri that was changed by setters
+    /// into an invalid one results in an error.
+    pub fn into_owned(self) -> Result<UriString, Error> {
+        UriString::parse(self.to_string())
+    }
+    /// Collect the raw `key=value` pairs of the query into a `Vec`,
+    /// see [`query_pairs`](struct.Uri.html#method.query_pairs).
+    /// Requires the `alloc` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use nom_uri::Uri;
+    ///
+    /// # fn run() -> Result<(), nom_uri::Error> {
+    /// let uri = Uri::parse("https://example.com/?a=1&b=2")?;
+    /// assert_eq!(uri.query_pairs_vec(), vec![("a", "1"), ("b", "2")]);
+    /// # Ok(())
+    /// # }
+    /// # run().unwrap();
+    /// ```
+    pub fn query_pairs_vec(&self) -> Vec<(&str, &str)> {
+        self.query_pairs().collect()
+    }
+}
+impl fmt::Display for UriString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+impl fmt::Debug for UriString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.as_uri(), f)
+    }
+}
@@ -114,6 +114,23 @@ fn uri_reference<'a, E: nom::error::ParseError<&'a [u8]>>(
 /// ```abnf
 /// relative-ref  = relative-part [ "?" query ] [ "#" fragment ]
 /// ```
+pub(crate) fn reference<'a, E: nom::error::ParseError<&'a [u8]>>(
+    i: &'a [u8],
+) -> IResult<&'a [u8], Reference<'a>, E> {
+    let (i, (a, p, q, f)) = relative_ref(i)?;
+    Ok((
+        i,
+        Reference {
+            authority: a,
+            path: p,
+            query: q,
+            fragment: f,
+        },
+    ))
+}
+/// ```abnf
+/// relative-ref  = relative-part [ "?" query ] [ "#" fragment ]
+/// ```
 fn relative_ref<'a, E: nom::error::ParseError<&'a [u8]>>(
     i: &'a [u8],
 ) -> IResult<&'a [u8], (Option<Authority>, Path, Option<Query>, Option<Fragment>), E> {
@@ -161,7 +178,11 @@ pub fn scheme<'a, E: nom::error::ParseError<&'a [u8]>>(i: &'a [u8]) -> IResult<&
 /// ```abnf
 /// authority     = [ userinfo "@" ] host [ ":" port ]
 /// ```
-pub(crate) fn authority<'a, E: nom::error::ParseError<&'a [u8]>>(
+/// The host can be empty (reg-name matches zero characters),
+/// so "//" is always followed by an authority:
+/// "scheme://" has an empty authority and "scheme:///path"
+/// an empty authority with the path "/path".
+pub fn authority<'a, E: nom::error::ParseError<&'a [u8]>>(
     i: &'a [u8],
 ) -> IResult<&'a [u8], Authority, E> {
     let (rest, (user_info, hos_t, por_t)) = tuple((
@@ -195,8 +216,45 @@ pub fn userinfo<'a, E: nom::error::ParseError<&'a [u8]>>(
 /// ```abnf
 /// host          = IP-literal / IPv4address / reg-name
 /// ```
+/// An IPv4address that is followed by further reg-name characters
+/// (like "127.0.0.1.com") is parsed as reg-name.
+/// Dotted decimals that are no IPv4address (like "999.1.1.1")
+/// are valid reg-names, too. Use [`host_strict`] to reject them.
 pub fn host<'a, E: nom::error::ParseError<&'a [u8]>>(i: &'a [u8]) -> IResult<&'a [u8], Host, E> {
-    alt((ip_literal, ip_v4_address, reg_name))(i)
+    alt((
+        ip_literal,
+        terminated(
+            ip_v4_address,
+            not(peek(alt((unreserved, pct_encoded, sub_delims)))),
+        ),
+        reg_name,
+    ))(i)
+}
+/// Like [`host`] but fail on a reg-name of four dot separated decimals
+/// (like "999.1.1.1"), which is most likely a mistyped IPv4address.
+pub fn host_strict<'a, E: nom::error::ParseError<&'a [u8]>>(
+    i: &'a [u8],
+) -> IResult<&'a [u8], Host<'a>, E> {
+    let (rest, host) = host(i)?;
+    if let Host::RegistryName(name) = host {
+        let mut parts = 0;
+        let dotted_decimal = name.split('.').all(|part| {
+            parts += 1;
+            !part.is_empty() && part.bytes().all(|byte| byte.is_ascii_digit())
+        });
+        if dotted_decimal && parts == 4 {
+            return Err(nom::Err::Error(E::from_error_kind(i, ErrorKind::Verify)));
+        }
+    }
+    Ok((rest, host))
+}
+/// Like [`host`] but parse everything except an IP-literal as reg-name,
+/// so "1.2.3.4" is a `RegistryName` instead of `V4`.
+/// Useful for systems that never address hosts by bare IPv4 addresses.
+pub fn host_registry_name<'a, E: nom::error::ParseError<&'a [u8]>>(
+    i: &'a [u8],
+) -> IResult<&'a [u8], Host<'a>, E> {
+    alt((ip_literal, reg_name))(i)
 }
 /// ```abnf
 /// port          = *DIGIT
@@ -244,12 +302,25 @@ fn ip_v_future<'a, E: nom::error::ParseError<&'a [u8]>>(
 ///               / [ *4( h16 ":" ) h16 ] "::"              (ls32 / IPv4address)
 ///               / [ *5( h16 ":" ) h16 ] "::"              h16
 /// ```
+///
+/// The address has to end at a character that cannot continue it,
+/// so that over-long addresses like "1:2:3:4:5:6:7:8:9" are rejected
+/// instead of being cut after the eighth group.
 pub fn ip_v6_address<'a, E: nom::error::ParseError<&'a [u8]>>(
     i: &'a [u8],
 ) -> IResult<&'a [u8], Host, E> {
-    let (i, o) = alt((ip_v6_long, ip_v6_short))(i)?;
+    let (i, o) = alt((
+        terminated(ip_v6_long, ip_v6_terminated),
+        terminated(ip_v6_short, ip_v6_terminated),
+    ))(i)?;
     Ok((i, Host::V6(o)))
 }
+/// Succeeds if the input does not continue with an address character.
+fn ip_v6_terminated<'a, E: nom::error::ParseError<&'a [u8]>>(
+    i: &'a [u8],
+) -> IResult<&'a [u8], (), E> {
+    not(peek(alt((hexdig, one_of(":.")))))(i)
+}
 /// ```abnf
 /// /                       "::" 5( h16 ":" ) (ls32 / IPv4address)
 /// / [               h16 ] "::" 4( h16 ":" ) (ls32 / IPv4address)
@@ -261,11 +332,11 @@ pub fn ip_v6_address<'a, E: nom::error::ParseError<&'a [u8]>>(
 /// / [ *6( h16 ":" ) h16 ] "::"
 /// ```
 fn ip_v6_short<'a, E: nom::error::ParseError<&'a [u8]>>(i: &'a [u8]) -> IResult<&'a [u8], &str, E> {
-    let (i, (left_colons, right_colons)) = alt((
+    let (_, (left_colons, right_colons)) = alt((
         separated_pair(
             opt(pair(many_str_m_n(0, 0, h16_colon), many_str_m_n(0, 0, h16))),
             tag("::"),
-            pair(many_str_m_n(4, 4, h16_colon), ip_v6_end),
+            pair(many_str_m_n(5, 5, h16_colon), ip_v6_end),
         ),
         separated_pair(
             opt(pair(many_str_m_n(0, 0, h16_colon), h16)),
@@ -307,15 +378,31 @@ fn ip_v6_short<'a, E: nom::error::ParseError<&'a [u8]>>(i: &'a [u8]) -> IResult<
         Some((l, r)) => l.len() + r.len(),
         None => 0,
     };
+    position += 2; // the "::"
     position += right_colons.0.len();
     position += right_colons.1.len();
-    Ok(split_input_to_str(i, position))
+    let (rest, o) = split_input_to_str(i, position);
+    // "::" replaces at least one group, so at most seven groups may be written out
+    if ip_v6_group_count(o) > 7 {
+        return Err(nom::Err::Error(E::from_error_kind(i, ErrorKind::Verify)));
+    }
+    Ok((rest, o))
+}
+/// Count the 16 bit groups written out in an IPv6 address.
+/// An embedded IPv4 address counts as two groups.
+fn ip_v6_group_count(address: &str) -> usize {
+    address
+        .split(':')
+        .filter(|group| !group.is_empty())
+        .map(|group| if group.contains('.') { 2 } else { 1 })
+        .sum()
 }
 /// ```abnf
 /// 6( h16 ":" ) (ls32 / IPv4address)
 /// ```
 fn ip_v6_long<'a, E: nom::error::ParseError<&'a [u8]>>(i: &'a [u8]) -> IResult<&'a [u8], &str, E> {
-    many_str_m_n(6, 6, ip_v6_end)(i)
+    let (_, (groups, end)) = pair(many_str_m_n(6, 6, h16_colon), ip_v6_end)(i)?;
+    Ok(split_input_to_str(i, groups.len() + end.len()))
 }
 /// (ls32 / IPv4address)
 fn ip_v6_end<'a, E: nom::error::ParseError<&'a [u8]>>(i: &'a [u8]) -> IResult<&'a [u8], &str, E> {
@@ -328,8 +415,9 @@ fn ip_v6_end<'a, E: nom::error::ParseError<&'a [u8]>>(i: &'a [u8]) -> IResult<&'
 /// ```abnf
 /// ( h16 ":" )
 /// ```
+/// The colon must not be the start of a "::".
 fn h16_colon<'a, E: nom::error::ParseError<&'a [u8]>>(i: &'a [u8]) -> IResult<&'a [u8], &str, E> {
-    let (_, (o1, _)) = pair(h16, char(':'))(i)?;
+    let (_, (o1, _)) = pair(h16, terminated(char(':'), not(char(':'))))(i)?;
     Ok(split_input_to_str(i, o1.len() + 1)) // one colon
 }
 /// ```abnf
@@ -338,12 +426,10 @@ fn h16_colon<'a, E: nom::error::ParseError<&'a [u8]>>(i: &'a [u8]) -> IResult<&'
 /// ```
 fn h16<'a, E: nom::error::ParseError<&'a [u8]>>(i: &'a [u8]) -> IResult<&'a [u8], &str, E> {
     let (rest, o) = hex_digit1(i)?;
+    if o.len() > 4 {
+        return Err(nom::Err::Error(E::from_error_kind(i, ErrorKind::HexDigit)));
+    }
     let o = unsafe { core::str::from_utf8_unchecked(o) }; // already parsed -> cannot fail
-    match u16::from_str_radix(o, 16) {
-        // u16 max_value() = FFFF => no extra value check
-        Err(_) => return Err(nom::Err::Error(E::from_error_kind(i, ErrorKind::Digit))),
-        _ => {}
-    };
     Ok((rest, o))
 }
 /// ```abnf
@@ -396,7 +482,7 @@ fn dec_octet<'a, E: nom::error::ParseError<&'a [u8]>>(i: &'a [u8]) -> IResult<&'
 /// reg-name      = *( unreserved / pct-encoded / sub-delims )
 /// ```
 fn reg_name<'a, E: nom::error::ParseError<&'a [u8]>>(i: &'a [u8]) -> IResult<&'a [u8], Host, E> {
-    let (_, position) = fold_many1(
+    let (_, position) = fold_many0(
         alt((unreserved, pct_encoded, sub_delims)),
         0,
         |mut pos: usize, _| {
@@ -655,6 +741,117 @@ fn ip_v4_test() {
     );
 }
 #[test]
+fn host_test() {
+    assert_eq!(
+        host::<(&[u8], ErrorKind)>(b"127.0.0.1/"),
+        Ok((&b"/"[..], Host::V4("127.0.0.1")))
+    );
+    assert_eq!(
+        host::<(&[u8], ErrorKind)>(b"127.0.0.1.com/"),
+        Ok((&b"/"[..], Host::RegistryName("127.0.0.1.com")))
+    );
+    assert_eq!(
+        host::<(&[u8], ErrorKind)>(b"/tmp"),
+        Ok((&b"/tmp"[..], Host::RegistryName("")))
+    );
+}
+#[test]
+fn host_registry_name_test() {
+    assert_eq!(
+        host::<(&[u8], ErrorKind)>(b"1.2.3.4"),
+        Ok((&b""[..], Host::V4("1.2.3.4")))
+    );
+    assert_eq!(
+        host_registry_name::<(&[u8], ErrorKind)>(b"1.2.3.4:80"),
+        Ok((&b":80"[..], Host::RegistryName("1.2.3.4")))
+    );
+    assert_eq!(
+        host_registry_name::<(&[u8], ErrorKind)>(b"0x7f.1"),
+        Ok((&b""[..], Host::RegistryName("0x7f.1")))
+    );
+    assert_eq!(
+        host_registry_name::<(&[u8], ErrorKind)>(b"[::1]"),
+        Ok((&b""[..], Host::V6("::1")))
+    );
+}
+#[test]
+fn host_strict_test() {
+    assert_eq!(
+        host::<(&[u8], ErrorKind)>(b"999.1.1.1"),
+        Ok((&b""[..], Host::RegistryName("999.1.1.1")))
+    );
+    assert_eq!(
+        host_strict::<(&[u8], ErrorKind)>(b"999.1.1.1"),
+        Err(nom::Err::Error((&b"999.1.1.1"[..], ErrorKind::Verify)))
+    );
+    assert_eq!(
+        host_strict::<(&[u8], ErrorKind)>(b"1.2.3.4:80"),
+        Ok((&b":80"[..], Host::V4("1.2.3.4")))
+    );
+    assert_eq!(
+        host_strict::<(&[u8], ErrorKind)>(b"1.2.3.4.5"),
+        Ok((&b""[..], Host::RegistryName("1.2.3.4.5")))
+    );
+    assert_eq!(
+        host_strict::<(&[u8], ErrorKind)>(b"example.com"),
+        Ok((&b""[..], Host::RegistryName("example.com")))
+    );
+}
+#[test]
+fn ip_v6_test() {
+    assert_eq!(
+        ip_v6_address::<(&[u8], ErrorKind)>(b"::]"),
+        Ok((&b"]"[..], Host::V6("::")))
+    );
+    assert_eq!(
+        ip_v6_address::<(&[u8], ErrorKind)>(b"::1]"),
+        Ok((&b"]"[..], Host::V6("::1")))
+    );
+    assert_eq!(
+        ip_v6_address::<(&[u8], ErrorKind)>(b"2001:db8::1]"),
+        Ok((&b"]"[..], Host::V6("2001:db8::1")))
+    );
+    assert_eq!(
+        ip_v6_address::<(&[u8], ErrorKind)>(b"1:2:3:4:5:6:7:8]"),
+        Ok((&b"]"[..], Host::V6("1:2:3:4:5:6:7:8")))
+    );
+    assert_eq!(
+        ip_v6_address::<(&[u8], ErrorKind)>(b"1:2:3:4:5:6:1.2.3.4]"),
+        Ok((&b"]"[..], Host::V6("1:2:3:4:5:6:1.2.3.4")))
+    );
+    assert_eq!(
+        ip_v6_address::<(&[u8], ErrorKind)>(b"::ffff:192.168.0.1]"),
+        Ok((&b"]"[..], Host::V6("::ffff:192.168.0.1")))
+    );
+    assert!(ip_v6_address::<(&[u8], ErrorKind)>(b"1:2:3:4:5:6:7:8:9]").is_err());
+    assert!(ip_v6_address::<(&[u8], ErrorKind)>(b"1:2:3:4:5:6:1.2.3.4.5]").is_err());
+    assert!(ip_v6_address::<(&[u8], ErrorKind)>(b"12345::1]").is_err());
+}
+#[test]
+fn ip_v6_short_test() {
+    assert_eq!(
+        ip_v6_short::<(&[u8], ErrorKind)>(b"1:2:3:4:5:6:7::]"),
+        Ok((&b"]"[..], "1:2:3:4:5:6:7::"))
+    );
+    assert_eq!(
+        ip_v6_short::<(&[u8], ErrorKind)>(b"::2:3:4:5:6:7:8]"),
+        Ok((&b"]"[..], "::2:3:4:5:6:7:8"))
+    );
+    assert_eq!(
+        ip_v6_short::<(&[u8], ErrorKind)>(b"1::3:4:5:6:7:8]"),
+        Ok((&b"]"[..], "1::3:4:5:6:7:8"))
+    );
+    assert_eq!(
+        ip_v6_short::<(&[u8], ErrorKind)>(b"::3:4:5:6:1.2.3.4]"),
+        Ok((&b"]"[..], "::3:4:5:6:1.2.3.4"))
+    );
+    assert!(ip_v6_address::<(&[u8], ErrorKind)>(b"1:2:3:4:5:6:7::8]").is_err());
+    assert!(ip_v6_address::<(&[u8], ErrorKind)>(b"1:2:3:4::5:6:7:8]").is_err());
+    assert!(ip_v6_address::<(&[u8], ErrorKind)>(b"::2:3:4:5:6:7:1.2.3.4]").is_err());
+    assert_eq!(ip_v6_group_count("1:2:3:4:5:6:7::"), 7);
+    assert_eq!(ip_v6_group_count("::ffff:1.2.3.4"), 3);
+}
+#[test]
 fn path_absolute_test() {
     assert_eq!(
         path_absolute(b"abc/def//"),
@@ -688,6 +885,78 @@ fn path_abempty_test() {
     );
 }
 #[test]
+fn validate_all_test() {
+    let uri = Uri {
+        scheme: "1http",
+        authority: Some(Authority {
+            userinfo: None,
+            host: Host::RegistryName("example.com"),
+            port: None,
+        }),
+        path: Path::Rootless("a/b"),
+        query: Some(Query("q")),
+        fragment: None,
+    };
+    let errors = uri.validate_all();
+    assert_eq!(errors.iter().flatten().count(), 2);
+    assert!(errors[0].is_some());
+    assert!(errors[2].is_some());
+}
+#[test]
+fn uri_empty_path_test() {
+    assert_eq!(
+        uri::<(&[u8], ErrorKind)>(b"foo://user@host:8080"),
+        Ok((
+            &[][..],
+            Uri {
+                scheme: "foo",
+                authority: Some(Authority {
+                    userinfo: Some("user"),
+                    host: Host::RegistryName("host"),
+                    port: Some("8080"),
+                }),
+                path: Path::AbEmpty(""),
+                query: None,
+                fragment: None,
+            }
+        ))
+    );
+}
+#[test]
+fn empty_path_eq_test() {
+    use core::hash::{Hash, Hasher};
+    struct TestHasher(u64);
+    impl Hasher for TestHasher {
+        fn finish(&self) -> u64 {
+            self.0
+        }
+        fn write(&mut self, bytes: &[u8]) {
+            for byte in bytes {
+                self.0 = self.0.wrapping_mul(31).wrapping_add(u64::from(*byte));
+            }
+        }
+    }
+    let uri = |path| Uri {
+        scheme: "foo",
+        authority: Some(Authority {
+            userinfo: None,
+            host: Host::RegistryName("host"),
+            port: None,
+        }),
+        path,
+        query: None,
+        fragment: None,
+    };
+    let (empty, abempty) = (uri(Path::Empty), uri(Path::AbEmpty("")));
+    assert_eq!(empty, abempty);
+    assert_eq!(empty.cmp(&abempty), core::cmp::Ordering::Equal);
+    let (mut a, mut b) = (TestHasher(0), TestHasher(0));
+    empty.hash(&mut a);
+    abempty.hash(&mut b);
+    assert_eq!(a.finish(), b.finish());
+    assert_ne!(uri(Path::Empty), uri(Path::AbEmpty("/")));
+}
+#[test]
 fn fragment_test() {
     unsafe {
         assert_eq!(
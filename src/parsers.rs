@@ -0,0 +1,96 @@
+/*!
+Parsers for single uri components, built with [nom](https://crates.io/crates/nom).
+
+They can be used to validate standalone components
+or to build custom combinators.
+Every parser takes a byte slice and returns the unparsed rest together with the
+parsed component. A parser stops at the first byte that does not belong to its
+component, so check the rest if the whole input should be consumed.
+
+The error type `E` can be any `nom::error::ParseError<&[u8]>`.
+[`DefaultError`](type.DefaultError.html) is the simplest choice.
+
+# Examples
+
+```rust
+use nom_uri::parsers::{self, DefaultError};
+use nom_uri::Host;
+
+let (rest, host) = parsers::host::<DefaultError>(b"example.com:8080").unwrap();
+assert_eq!(host, Host::RegistryName("example.com"));
+assert_eq!(rest, b":8080");
+```
+
+The `is_valid_*` functions require the whole input to be the component:
+
+```rust
+use nom_uri::parsers;
+
+assert!(parsers::is_valid_host("example.com"));
+assert!(!parsers::is_valid_host("example.com:8080"));
+```
+*/
+use crate::{parse_complete, parser, Fragment, Path, Query};
+use nom::IResult;
+
+pub use crate::parser::{authority, host, host_registry_name, host_strict, port, scheme, userinfo};
+
+/// The error type that nom uses by default:
+/// the input at the error position and the kind of the error.
+pub type DefaultError<'a> = (&'a [u8], nom::error::ErrorKind);
+
+/// Parse a path of any kind (absolute, rootless, empty...).
+pub fn path<'a, E: nom::error::ParseError<&'a [u8]>>(i: &'a [u8]) -> IResult<&'a [u8], &'a str, E> {
+    let (rest, path) = parser::path(i)?;
+    let path = match path {
+        Path::AbEmpty(p) | Path::Absolute(p) | Path::NoScheme(p) | Path::Rootless(p) => p,
+        Path::Empty => "",
+    };
+    Ok((rest, path))
+}
+/// Parse a query without the leading '?'.
+pub fn query<'a, E: nom::error::ParseError<&'a [u8]>>(
+    i: &'a [u8],
+) -> IResult<&'a [u8], &'a str, E> {
+    let (rest, Query(query)) = parser::query(i)?;
+    Ok((rest, query))
+}
+/// Parse a fragment without the leading '#'.
+pub fn fragment<'a, E: nom::error::ParseError<&'a [u8]>>(
+    i: &'a [u8],
+) -> IResult<&'a [u8], &'a str, E> {
+    let (rest, Fragment(fragment)) = parser::fragment(i)?;
+    Ok((rest, fragment))
+}
+
+/// Return whether the whole string is a valid scheme.
+pub fn is_valid_scheme(scheme: &str) -> bool {
+    parse_complete(parser::scheme, scheme.as_bytes()).is_ok()
+}
+/// Return whether the whole string is a valid host.
+/// IPv6 addresses have to be enclosed in '[' and ']' brackets.
+/// The empty host is valid.
+pub fn is_valid_host(host: &str) -> bool {
+    parse_complete(parser::host, host.as_bytes()).is_ok()
+}
+/// Return whether the whole string is a valid port.
+/// The empty port is valid.
+pub fn is_valid_port(port: &str) -> bool {
+    parse_complete(parser::port, port.as_bytes()).is_ok()
+}
+/// Return whether the whole string is a valid userinfo.
+pub fn is_valid_userinfo(userinfo: &str) -> bool {
+    parse_complete(parser::userinfo, userinfo.as_bytes()).is_ok()
+}
+/// Return whether the whole string is a valid path of any kind.
+pub fn is_valid_path(path: &str) -> bool {
+    parse_complete(parser::path, path.as_bytes()).is_ok()
+}
+/// Return whether the whole string is a valid query without the leading '?'.
+pub fn is_valid_query(query: &str) -> bool {
+    parse_complete(parser::query, query.as_bytes()).is_ok()
+}
+/// Return whether the whole string is a valid fragment without the leading '#'.
+pub fn is_valid_fragment(fragment: &str) -> bool {
+    parse_complete(parser::fragment, fragment.as_bytes()).is_ok()
+}
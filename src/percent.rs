@@ -0,0 +1,97 @@
+use super::*;
+use core::fmt;
+
+/// Iterator over the bytes of a percent-encoded string with every
+/// valid escape ("%41") replaced by the byte it encodes.
+///
+/// A '%' that is not followed by two hex digits is returned unchanged.
+#[derive(Clone)]
+pub(crate) struct PercentDecode<'a> {
+    bytes: &'a [u8],
+}
+impl<'a> PercentDecode<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self {
+            bytes: input.as_bytes(),
+        }
+    }
+}
+impl<'a> Iterator for PercentDecode<'a> {
+    type Item = u8;
+    fn next(&mut self) -> Option<u8> {
+        let (&first, rest) = self.bytes.split_first()?;
+        if first == b'%' && rest.len() >= 2 {
+            if let (Some(high), Some(low)) = (hex_value(rest[0]), hex_value(rest[1])) {
+                self.bytes = &rest[2..];
+                return Some(high << 4 | low);
+            }
+        }
+        self.bytes = rest;
+        Some(first)
+    }
+}
+fn hex_value(byte: u8) -> Option<u8> {
+    match byte {
+        b'0'..=b'9' => Some(byte - b'0'),
+        b'a'..=b'f' => Some(byte - b'a' + 10),
+        b'A'..=b'F' => Some(byte - b'A' + 10),
+        _ => None,
+    }
+}
+/// Compare a percent-encoded string with a plain string
+/// without decoding into a buffer.
+pub(crate) fn decoded_eq(encoded: &str, plain: &str) -> bool {
+    PercentDecode::new(encoded).eq(plain.bytes())
+}
+/// Percent-decode `input` into `buffer` and return the decoded part of the buffer.
+///
+/// Fails if the buffer is too small or the decoded bytes are not valid utf8.
+pub(crate) fn decode<'b>(input: &str, buffer: &'b mut [u8]) -> Result<&'b str, Error> {
+    let mut decoded = PercentDecode::new(input);
+    let mut len = 0;
+    for byte in decoded.by_ref() {
+        match buffer.get_mut(len) {
+            Some(slot) => *slot = byte,
+            None => {
+                return Err(Error::BufferTooSmall {
+                    needed: len + 1 + decoded.count(),
+                    available: buffer.len(),
+                })
+            }
+        }
+        len += 1;
+    }
+    match core::str::from_utf8(&buffer[..len]) {
+        Ok(decoded) => Ok(decoded),
+        Err(e) => Err(Error::Conversion(e)),
+    }
+}
+/// Write `input` to `w`, percent-encoding every byte that is not `allowed`.
+pub(crate) fn encode<W: fmt::Write>(
+    input: &str,
+    allowed: fn(u8) -> bool,
+    w: &mut W,
+) -> fmt::Result {
+    for &byte in input.as_bytes() {
+        if allowed(byte) {
+            w.write_char(byte as char)?;
+        } else {
+            write!(w, "%{:02X}", byte)?;
+        }
+    }
+    Ok(())
+}
+/// unreserved    = ALPHA / DIGIT / "-" / "." / "_" / "~"
+pub(crate) fn is_unreserved(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || b"-._~".contains(&byte)
+}
+/// sub-delims    = "!" / "$" / "&" / "'" / "(" / ")" / "*" / "+" / "," / ";" / "="
+pub(crate) fn is_sub_delim(byte: u8) -> bool {
+    b"!$&'()*+,;=".contains(&byte)
+}
+/// pchar         = unreserved / pct-encoded / sub-delims / ":" / "@"
+///
+/// Without pct-encoded, since a literal '%' has to be encoded.
+pub(crate) fn is_pchar(byte: u8) -> bool {
+    is_unreserved(byte) || is_sub_delim(byte) || byte == b':' || byte == b'@'
+}
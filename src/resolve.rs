@@ -0,0 +1,171 @@
+/// Reference resolution based on http://www.faqs.org/rfcs/rfc3986.html
+/// Section 5.2.  Relative Resolution
+use super::*;
+use core::fmt;
+use core::fmt::Write;
+
+/// Resolve `reference` against `base` and write the target uri to the buffer.
+///
+/// ```text
+/// if defined(R.authority) then
+///    T.authority = R.authority;
+///    T.path      = remove_dot_segments(R.path);
+///    T.query     = R.query;
+/// else
+///    if (R.path == "") then
+///       T.path = Base.path;
+///       if defined(R.query) then
+///          T.query = R.query;
+///       else
+///          T.query = Base.query;
+///       endif;
+///    else
+///       if (R.path starts-with "/") then
+///          T.path = remove_dot_segments(R.path);
+///       else
+///          T.path = merge(Base.path, R.path);
+///          T.path = remove_dot_segments(T.path);
+///       endif;
+///       T.query = R.query;
+///    endif;
+///    T.authority = Base.authority;
+/// endif;
+/// T.scheme = Base.scheme;
+/// T.fragment = R.fragment;
+/// ```
+pub(crate) fn resolve<'b>(
+    base: &Uri,
+    reference: &Reference,
+    buffer: &'b mut [u8],
+) -> Result<Uri<'b>, Error> {
+    let authority = reference.authority.or(base.authority);
+    let reference_path = reference.path.as_str();
+    let keep_base_path = reference.authority.is_none() && reference_path.is_empty();
+    let query = if keep_base_path {
+        reference.query.or(base.query)
+    } else {
+        reference.query
+    };
+    // the dot-segments are removed in place after writing,
+    // so a too small buffer reports the length before the removal
+    let mut path = 0..0;
+    let mut written = formater::write_with(buffer, |w| {
+        write!(w, "{}:", base.scheme)?;
+        if let Some(authority) = authority {
+            write!(w, "//{}", authority)?;
+        }
+        let start = w.written();
+        if keep_base_path {
+            write!(w, "{}", base.path)?;
+        } else if reference.authority.is_some() || reference_path.starts_with('/') {
+            w.write_str(reference_path)?;
+        } else {
+            merge(base, reference_path, w)?;
+        }
+        path = start..w.written();
+        if let Some(query) = query {
+            write!(w, "?{}", query)?;
+        }
+        if let Some(fragment) = reference.fragment {
+            write!(w, "#{}", fragment)?;
+        }
+        Ok(())
+    })?;
+    if !keep_base_path {
+        written = remove_path_dot_segments(buffer, path, written);
+    }
+    let buffer: &'b [u8] = buffer;
+    Uri::parse_bytes(&buffer[..written])
+}
+/// Write the merged path of the base and a relative path
+/// (RFC 3986 Section 5.2.3).
+fn merge<W: Write>(base: &Uri, path: &str, w: &mut W) -> fmt::Result {
+    let base_path = base.path();
+    if base.authority.is_some() && base_path.is_empty() {
+        write!(w, "/{}", path)
+    } else {
+        let directory = match base_path.rfind('/') {
+            Some(i) => &base_path[..=i],
+            None => "",
+        };
+        write!(w, "{}{}", directory, path)
+    }
+}
+/// Remove the dot-segments of the path at `path` in the first `written` bytes
+/// of the buffer, move the rest of the output behind the new path
+/// and return the new length of the output.
+pub(crate) fn remove_path_dot_segments(
+    buffer: &mut [u8],
+    path: core::ops::Range<usize>,
+    written: usize,
+) -> usize {
+    let len = remove_dot_segments(&mut buffer[path.clone()]);
+    buffer.copy_within(path.end..written, path.start + len);
+    written - (path.len() - len)
+}
+/// Write the formatted arguments to the buffer and return the number of written bytes.
+pub(crate) fn write_to(buffer: &mut [u8], args: fmt::Arguments) -> Result<usize, Error> {
+    formater::write_with(buffer, |w| w.write_fmt(args))
+}
+/// Remove the "." and ".." segments of a path in place
+/// (RFC 3986 Section 5.2.4) and return the new length of the path.
+///
+/// Segments are compared byte by byte, so percent-encoded dots
+/// ("%2E") are no dot-segments.
+pub(crate) fn remove_dot_segments(path: &mut [u8]) -> usize {
+    // path[..output] is the output buffer, path[input..] the input buffer
+    let mut input = 0;
+    let mut output = 0;
+    let len = path.len();
+    while input < len {
+        let rest = &path[input..];
+        if rest.starts_with(b"../") {
+            input += 3;
+        } else if rest.starts_with(b"./") || rest.starts_with(b"/./") {
+            input += 2;
+        } else if rest == b"/." {
+            input = len;
+            path[output] = b'/';
+            output += 1;
+        } else if rest.starts_with(b"/../") {
+            input += 3;
+            output = last_segment_start(&path[..output]);
+        } else if rest == b"/.." {
+            input = len;
+            output = last_segment_start(&path[..output]);
+            path[output] = b'/';
+            output += 1;
+        } else if rest == b"." || rest == b".." {
+            input = len;
+        } else {
+            // move the first segment (with its leading '/') to the output
+            let end = match rest.iter().skip(1).position(|&b| b == b'/') {
+                Some(i) => input + 1 + i,
+                None => len,
+            };
+            path.copy_within(input..end, output);
+            output += end - input;
+            input = end;
+        }
+    }
+    output
+}
+/// Position of the last '/' in the output, the start of the segment to remove.
+fn last_segment_start(output: &[u8]) -> usize {
+    output.iter().rposition(|&b| b == b'/').unwrap_or(0)
+}
+#[test]
+fn remove_dot_segments_test() {
+    fn remove<'a>(path: &str, buffer: &'a mut [u8]) -> &'a str {
+        buffer[..path.len()].copy_from_slice(path.as_bytes());
+        let len = remove_dot_segments(&mut buffer[..path.len()]);
+        core::str::from_utf8(&buffer[..len]).unwrap()
+    }
+    let buffer = &mut [0u8; 30][..];
+    assert_eq!(remove("/a/b/c/./../../g", buffer), "/a/g");
+    assert_eq!(remove("mid/content=5/../6", buffer), "mid/6");
+    assert_eq!(remove("/b/c/.", buffer), "/b/c/");
+    assert_eq!(remove("/b/c/..", buffer), "/b/");
+    assert_eq!(remove("/../g", buffer), "/g");
+    assert_eq!(remove("/a/%2E%2E/b", buffer), "/a/%2E%2E/b");
+}
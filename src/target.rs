@@ -0,0 +1,65 @@
+/// Request targets of HTTP/1.1 based on https://tools.ietf.org/html/rfc7230
+/// Section 5.3.  Request Target
+use super::*;
+
+/// The target of an HTTP request line.
+///
+/// # Examples
+///
+/// ```rust
+/// use nom_uri::RequestTarget;
+///
+/// # fn run() -> Result<(), nom_uri::Error> {
+/// let target = RequestTarget::parse("/where?q=now")?;
+/// assert_eq!(
+///     target,
+///     RequestTarget::Origin {
+///         path: "/where",
+///         query: Some("q=now")
+///     }
+/// );
+/// assert_eq!(RequestTarget::parse("*")?, RequestTarget::Asterisk);
+/// # Ok(())
+/// # }
+/// # run().unwrap();
+/// ```
+#[derive(Debug, PartialEq, Eq)]
+pub enum RequestTarget<'a> {
+    /// `absolute-path [ "?" query ]`, the usual target of a request.
+    Origin {
+        path: &'a str,
+        query: Option<&'a str>,
+    },
+    /// An absolute uri, used for requests to proxies.
+    Absolute(Uri<'a>),
+    /// `host:port`, used for CONNECT requests.
+    Authority(Authority<'a>),
+    /// `*`, used for server wide OPTIONS requests.
+    Asterisk,
+}
+
+impl<'a> RequestTarget<'a> {
+    /// Parse and classify a request target.
+    ///
+    /// Since `host:port` is a valid absolute uri as well,
+    /// the authority-form is tried before the absolute-form.
+    pub fn parse(input: &'a str) -> Result<Self, Error> {
+        if input == "*" {
+            return Ok(RequestTarget::Asterisk);
+        }
+        if input.starts_with('/') {
+            let reference = Reference::parse(input)?;
+            if reference.authority.is_some() || reference.fragment.is_some() {
+                return Err(Error::ParseError);
+            }
+            return Ok(RequestTarget::Origin {
+                path: reference.path.as_str(),
+                query: reference.query.map(|Query(query)| query),
+            });
+        }
+        match Uri::parse_authority_form(input) {
+            Ok(authority) => Ok(RequestTarget::Authority(authority)),
+            Err(_) => Ok(RequestTarget::Absolute(Uri::parse(input)?)),
+        }
+    }
+}